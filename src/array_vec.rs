@@ -0,0 +1,447 @@
+//! A fixed-capacity, stack-allocated vector built on top of `Array`
+use ::Array;
+
+/// A stack-allocated vector with a fixed capacity of `A::capacity()` elements
+///
+/// Unlike `Iter`, which only ever yields each element once, `ArrayVec` allows pushing and
+/// popping from the live prefix while leaving the remaining capacity uninitialised. The backing
+/// storage is kept as `MaybeUninit<A>` and only ever touched through `Array`'s raw-pointer
+/// accessors (`raw_get_ptr`/`raw_get_mut_ptr`), so a partially-filled vector never materialises
+/// an actual (possibly invalid) `A` value.
+pub struct ArrayVec<A: Array>
+{
+	data: ::core::mem::MaybeUninit<A>,
+	len: usize,
+}
+impl<A: Array> ArrayVec<A>
+{
+	/// Create a new, empty vector
+	pub fn new() -> Self {
+		ArrayVec {
+			data: ::core::mem::MaybeUninit::uninit(),
+			len: 0,
+			}
+	}
+	/// Maximum number of elements this vector can hold
+	pub fn capacity(&self) -> usize {
+		A::capacity()
+	}
+	/// Number of elements currently stored
+	pub fn len(&self) -> usize {
+		self.len
+	}
+	/// Returns `true` if the vector contains no elements
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+	/// Appends `item`, panicking if the vector is already at capacity
+	pub fn push(&mut self, item: A::Item) {
+		if self.try_push(item).is_err() {
+			panic!("ArrayVec::push: at capacity ({})", A::capacity());
+		}
+	}
+	/// Appends `item`, returning it back in `Err` if the vector is already at capacity
+	pub fn try_push(&mut self, item: A::Item) -> Result<(), A::Item> {
+		if self.len == A::capacity() {
+			Err(item)
+		}
+		else {
+			// SAFE: `len < capacity`, and slots at/after `len` are never otherwise read
+			unsafe {
+				::core::ptr::write(A::raw_get_mut_ptr(self.data.as_mut_ptr(), self.len), item);
+			}
+			self.len += 1;
+			Ok(())
+		}
+	}
+	/// Removes and returns the last element, or `None` if the vector is empty
+	pub fn pop(&mut self) -> Option<A::Item> {
+		if self.len == 0 {
+			None
+		}
+		else {
+			self.len -= 1;
+			// SAFE: Slot `len` was initialised by `push`/`try_push`, and is now excluded from
+			// the live range so it won't be read again
+			Some(unsafe { ::core::ptr::read(A::raw_get_ptr(self.data.as_ptr(), self.len)) })
+		}
+	}
+	/// Drops all elements, leaving the vector empty
+	pub fn clear(&mut self) {
+		// SAFE: `0 .. len` are exactly the live elements
+		for i in 0 .. self.len {
+			unsafe {
+				::core::ptr::drop_in_place(A::raw_get_mut_ptr(self.data.as_mut_ptr(), i));
+			}
+		}
+		self.len = 0;
+	}
+	/// Returns the live elements as a slice
+	pub fn as_slice(&self) -> &[A::Item] {
+		// SAFE: `0 .. len` are exactly the live elements
+		unsafe {
+			::core::slice::from_raw_parts(A::raw_get_ptr(self.data.as_ptr(), 0), self.len)
+		}
+	}
+	/// Returns the live elements as a mutable slice
+	pub fn as_mut_slice(&mut self) -> &mut [A::Item] {
+		// SAFE: `0 .. len` are exactly the live elements
+		unsafe {
+			::core::slice::from_raw_parts_mut(A::raw_get_mut_ptr(self.data.as_mut_ptr(), 0), self.len)
+		}
+	}
+	/// Removes the elements in `range`, returning them as an iterator
+	///
+	/// If the returned `Drain` is dropped before being fully consumed, the remaining elements
+	/// in `range` are dropped in place, and the untouched tail is shifted down to close the gap
+	/// (exactly as if the drain had been iterated to completion).
+	pub fn drain<R>(&mut self, range: R) -> Drain<'_, A>
+	where
+		R: ::core::ops::RangeBounds<usize>
+	{
+		let len = self.len;
+		let start = match range.start_bound() {
+			::core::ops::Bound::Included(&n) => n,
+			::core::ops::Bound::Excluded(&n) => n + 1,
+			::core::ops::Bound::Unbounded => 0,
+			};
+		let end = match range.end_bound() {
+			::core::ops::Bound::Included(&n) => n + 1,
+			::core::ops::Bound::Excluded(&n) => n,
+			::core::ops::Bound::Unbounded => len,
+			};
+		assert!(start <= end && end <= len, "ArrayVec::drain: range out of bounds");
+		// Shrink `len` to `start` up-front, so a leaked (mem::forget'd) `Drain` can't lead to the
+		// already-yielded elements of `start .. end` being dropped a second time by the vector
+		// (the not-yet-yielded elements and the `end .. orig_len` tail are then leaked, not dropped)
+		self.len = start;
+		Drain {
+			vec: self,
+			idx: start,
+			end,
+			orig_len: len,
+			}
+	}
+}
+impl<A: Array> Default for ArrayVec<A>
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl<A: Array> From<A> for ArrayVec<A>
+{
+	fn from(arr: A) -> Self {
+		let len = arr.len();
+		// SAFE: `arr` is a fully-valid, fully-initialised `A`
+		ArrayVec { data: ::core::mem::MaybeUninit::new(arr), len }
+	}
+}
+impl<A: Array> ::core::ops::Deref for ArrayVec<A>
+{
+	type Target = [A::Item];
+	fn deref(&self) -> &[A::Item] {
+		self.as_slice()
+	}
+}
+impl<A: Array> ::core::ops::DerefMut for ArrayVec<A>
+{
+	fn deref_mut(&mut self) -> &mut [A::Item] {
+		self.as_mut_slice()
+	}
+}
+impl<A: Array> Drop for ArrayVec<A>
+{
+	fn drop(&mut self) {
+		self.clear();
+	}
+}
+impl<A: Array> IntoIterator for ArrayVec<A>
+{
+	type Item = A::Item;
+	type IntoIter = IntoIter<A>;
+	fn into_iter(self) -> IntoIter<A> {
+		let back = self.len;
+		// SAFE: `data` (a `MaybeUninit<A>`, valid regardless of what it contains) is copied out,
+		// then `self` is forgotten so its `Drop` impl doesn't also try to drop the live prefix
+		let data = unsafe { ::core::ptr::read(&self.data) };
+		::core::mem::forget(self);
+		IntoIter { data, front: 0, back }
+	}
+}
+impl<A: Array> Extend<A::Item> for ArrayVec<A>
+{
+	/// Pushes each item in turn; like `push`, extending past capacity panics
+	fn extend<I: IntoIterator<Item=A::Item>>(&mut self, iter: I) {
+		for item in iter {
+			self.push(item);
+		}
+	}
+}
+impl<A: Array> ::core::iter::FromIterator<A::Item> for ArrayVec<A>
+{
+	/// Collects up to `A::capacity()` items; like `push`, collecting more than capacity panics
+	fn from_iter<I: IntoIterator<Item=A::Item>>(iter: I) -> Self {
+		let mut rv = Self::new();
+		rv.extend(iter);
+		rv
+	}
+}
+
+/// By-value iterator over an `ArrayVec`'s live prefix, returned by `ArrayVec::into_iter`
+///
+/// Mirrors `Iter`'s read-from-either-end-then-drop-remainder behaviour, but (unlike `Iter`) never holds
+/// an actual `A` value, since the tail beyond the original `len` may be uninitialised.
+pub struct IntoIter<A: Array>
+{
+	data: ::core::mem::MaybeUninit<A>,
+	front: usize,
+	back: usize,
+}
+impl<A: Array> Iterator for IntoIter<A>
+{
+	type Item = A::Item;
+	fn next(&mut self) -> Option<A::Item> {
+		if self.front == self.back {
+			None
+		}
+		else {
+			// SAFE: Only ever read in sequence, and never double-read
+			let rv = Some(unsafe {
+				::core::ptr::read(A::raw_get_ptr(self.data.as_ptr(), self.front))
+				});
+			self.front += 1;
+			rv
+		}
+	}
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.back - self.front;
+		(len, Some(len))
+	}
+}
+impl<A: Array> DoubleEndedIterator for IntoIter<A>
+{
+	fn next_back(&mut self) -> Option<A::Item> {
+		if self.front == self.back {
+			None
+		}
+		else {
+			self.back -= 1;
+			// SAFE: Only ever read in sequence, and never double-read
+			Some(unsafe {
+				::core::ptr::read(A::raw_get_ptr(self.data.as_ptr(), self.back))
+				})
+		}
+	}
+}
+impl<A: Array> ExactSizeIterator for IntoIter<A>
+{
+	fn len(&self) -> usize {
+		self.back - self.front
+	}
+}
+impl<A: Array> ::core::iter::FusedIterator for IntoIter<A>
+{
+}
+impl<A: Array> Drop for IntoIter<A>
+{
+	fn drop(&mut self) {
+		// SAFE: `front..back` is exactly the range of live, not-yet-read elements
+		for i in self.front .. self.back {
+			unsafe {
+				::core::ptr::drop_in_place(A::raw_get_mut_ptr(self.data.as_mut_ptr(), i));
+			}
+		}
+	}
+}
+
+/// Iterator returned by `ArrayVec::drain`
+pub struct Drain<'a, A: Array + 'a>
+{
+	vec: &'a mut ArrayVec<A>,
+	idx: usize,
+	end: usize,
+	orig_len: usize,
+}
+impl<'a, A: Array> Iterator for Drain<'a, A>
+{
+	type Item = A::Item;
+	fn next(&mut self) -> Option<A::Item> {
+		if self.idx == self.end {
+			None
+		}
+		else {
+			// SAFE: `idx .. end` is exactly the not-yet-yielded part of the drained range
+			let rv = Some(unsafe { ::core::ptr::read(A::raw_get_ptr(self.vec.data.as_ptr(), self.idx)) });
+			self.idx += 1;
+			rv
+		}
+	}
+}
+impl<'a, A: Array> Drop for Drain<'a, A>
+{
+	fn drop(&mut self) {
+		// SAFE: `idx .. end` are exactly the drained-but-not-yielded elements
+		for i in self.idx .. self.end {
+			unsafe {
+				::core::ptr::drop_in_place(A::raw_get_mut_ptr(self.vec.data.as_mut_ptr(), i));
+			}
+		}
+		// Close the gap by shifting the untouched tail `end .. orig_len` down to `vec.len ..`
+		let tail_len = self.orig_len - self.end;
+		if tail_len > 0 {
+			// SAFE: `end .. orig_len` is the live, untouched tail; `vec.len == start` is free
+			unsafe {
+				let src = A::raw_get_mut_ptr(self.vec.data.as_mut_ptr(), self.end);
+				let dst = A::raw_get_mut_ptr(self.vec.data.as_mut_ptr(), self.vec.len);
+				::core::ptr::copy(src, dst, tail_len);
+			}
+		}
+		self.vec.len += tail_len;
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::ArrayVec;
+	use ::ArrayExt;
+
+	struct DropTrace<'a>(&'a ::core::cell::Cell<isize>);
+	impl<'a> Drop for DropTrace<'a>
+	{
+		fn drop(&mut self) { self.0.set(self.0.get() + 1); }
+	}
+
+	#[test]
+	fn push_pop()
+	{
+		let mut v: ArrayVec<[u32; 4]> = ArrayVec::new();
+		assert_eq!( v.len(), 0 );
+		v.push(1);
+		v.push(2);
+		assert_eq!( v.as_slice(), &[1, 2] );
+		assert_eq!( v.pop(), Some(2) );
+		assert_eq!( v.pop(), Some(1) );
+		assert_eq!( v.pop(), None );
+	}
+
+	#[test]
+	fn try_push_past_capacity()
+	{
+		let mut v: ArrayVec<[u32; 2]> = ArrayVec::new();
+		v.push(1);
+		v.push(2);
+		assert_eq!( v.try_push(3), Err(3) );
+	}
+
+	#[test]
+	#[should_panic]
+	fn push_past_capacity_panics()
+	{
+		let mut v: ArrayVec<[u32; 1]> = ArrayVec::new();
+		v.push(1);
+		v.push(2);
+	}
+
+	#[test]
+	fn drop_only_touches_live_prefix()
+	{
+		let v = Default::default();
+		let mut av: ArrayVec<[DropTrace; 4]> = ArrayVec::new();
+		av.push(DropTrace(&v));
+		av.push(DropTrace(&v));
+		assert_eq!( v.get(), 0 );
+		drop(av);
+		assert_eq!( v.get(), 2 );
+	}
+
+	#[test]
+	fn from_full_array()
+	{
+		let v: ArrayVec<[u32; 3]> = ArrayVec::from([1, 2, 3]);
+		assert_eq!( v.as_slice(), &[1, 2, 3] );
+	}
+
+	#[test]
+	fn into_iter_yields_live_prefix_only()
+	{
+		let mut v: ArrayVec<[u32; 4]> = ArrayVec::new();
+		v.push(1);
+		v.push(2);
+		let mut it = v.into_iter();
+		assert_eq!( it.next(), Some(1) );
+		assert_eq!( it.next(), Some(2) );
+		assert_eq!( it.next(), None );
+	}
+
+	#[test]
+	fn into_iter_drops_only_live_prefix()
+	{
+		let v = Default::default();
+		let mut av: ArrayVec<[DropTrace; 4]> = ArrayVec::new();
+		av.push(DropTrace(&v));
+		av.push(DropTrace(&v));
+		let mut it = av.into_iter();
+		assert!( it.next().is_some() );
+		assert_eq!( v.get(), 1 );
+		drop(it);
+		assert_eq!( v.get(), 2 );
+	}
+
+	#[test]
+	fn extend_fills_remaining_capacity()
+	{
+		let mut v: ArrayVec<[u32; 4]> = ArrayVec::new();
+		v.push(1);
+		v.extend([2, 3, 4].into_iter());
+		assert_eq!( v.as_slice(), &[1, 2, 3, 4] );
+	}
+
+	#[test]
+	#[should_panic]
+	fn extend_past_capacity_panics()
+	{
+		let mut v: ArrayVec<[u32; 2]> = ArrayVec::new();
+		v.extend([1, 2, 3].into_iter());
+	}
+
+	#[test]
+	fn from_iter_collects()
+	{
+		let v: ArrayVec<[u32; 4]> = [1, 2, 3].into_iter().collect();
+		assert_eq!( v.as_slice(), &[1, 2, 3] );
+	}
+
+	#[test]
+	fn drain_removes_range_and_compacts()
+	{
+		let mut v: ArrayVec<[u32; 5]> = [1, 2, 3, 4, 5].into_iter().collect();
+		{
+			let mut d = v.drain(1..3);
+			assert_eq!( d.next(), Some(2) );
+			assert_eq!( d.next(), Some(3) );
+			assert_eq!( d.next(), None );
+		}
+		assert_eq!( v.as_slice(), &[1, 4, 5] );
+	}
+
+	#[test]
+	fn drain_drops_unconsumed_tail_and_compacts()
+	{
+		let v = Default::default();
+		let mut av: ArrayVec<[DropTrace; 4]> = ArrayVec::new();
+		av.push(DropTrace(&v));
+		av.push(DropTrace(&v));
+		av.push(DropTrace(&v));
+		av.push(DropTrace(&v));
+		{
+			let mut d = av.drain(1..3);
+			assert!( d.next().is_some() );
+			assert_eq!( v.get(), 1 );
+			// drop `d` here without consuming the second drained element
+		}
+		assert_eq!( v.get(), 2 );
+		assert_eq!( av.len(), 2 );
+	}
+}
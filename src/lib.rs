@@ -11,6 +11,9 @@
 //! }
 //! ```
 
+mod array_vec;
+pub use array_vec::ArrayVec;
+
 /// Extension trait for arrays providing 'into_iter'
 pub trait ArrayExt: Sized + Array
 {
@@ -23,40 +26,147 @@ pub unsafe trait Array
 	type Item;
 	fn len(&self) -> usize;
 	fn get_ptr(&self, ofs: usize) -> *const Self::Item;
+	fn get_mut_ptr(&mut self, ofs: usize) -> *mut Self::Item;
+	/// Number of elements, without requiring a (possibly not yet initialised) instance
+	fn capacity() -> usize where Self: Sized;
+	/// Computes a pointer to item `ofs`, given a pointer to (possibly uninitialised) storage
+	/// for `Self`
+	///
+	/// # Safety
+	/// `base` must be a valid, non-dangling pointer to (not necessarily initialised) storage
+	/// for `Self`, and `ofs` must be `< Self::capacity()`. Implementations must not read
+	/// through `base` itself, only derive a pointer to one of its elements.
+	unsafe fn raw_get_ptr(base: *const Self, ofs: usize) -> *const Self::Item where Self: Sized;
+	/// Mutable counterpart to `raw_get_ptr`
+	///
+	/// # Safety
+	/// Same preconditions as `raw_get_ptr`.
+	unsafe fn raw_get_mut_ptr(base: *mut Self, ofs: usize) -> *mut Self::Item where Self: Sized;
+	/// Build an array by calling `cb` once per index, in order
+	fn from_fn<F>(cb: F) -> Self
+	where
+		Self: Sized,
+		F: FnMut(usize) -> Self::Item;
+	/// Fallible version of `from_fn`, dropping any already-initialised elements if `cb`
+	/// returns an error partway through
+	fn try_from_fn<F, E>(cb: F) -> Result<Self, E>
+	where
+		Self: Sized,
+		F: FnMut(usize) -> Result<Self::Item, E>;
 }
 
 /// By-value array iterator
 //pub struct Iter<T, const usize N> {
 //    data: ::core::mem::ManuallyDrop<[T; N]>,
-//    ofs: usize,
+//    front: usize,
+//    back: usize,
 //}
 pub struct Iter<T: Array>
 {
 	data: ::core::mem::ManuallyDrop<T>,
-	ofs: usize,
+	front: usize,
+	back: usize,
+}
+impl<T: Array> Iter<T>
+{
+	/// Returns the elements that have not yet been yielded
+	pub fn as_slice(&self) -> &[T::Item]
+	{
+		// SAFE: `front..back` is exactly the range of live elements, and this borrows `self`.
+		// Uses `raw_get_ptr` rather than `get_ptr`, since `get_ptr`'s bounds-checked indexing
+		// would panic when `front == T::len()` (iterator fully drained, or a zero-length array)
+		unsafe {
+			::core::slice::from_raw_parts(T::raw_get_ptr(&*self.data, self.front), self.back - self.front)
+		}
+	}
+	/// Returns the elements that have not yet been yielded, allowing them to be mutated in-place
+	pub fn as_mut_slice(&mut self) -> &mut [T::Item]
+	{
+		let front = self.front;
+		let len = self.back - front;
+		// SAFE: `front..back` is exactly the range of live elements, and this borrows `self`.
+		// Uses `raw_get_mut_ptr` rather than `get_mut_ptr`, since `get_mut_ptr`'s bounds-checked
+		// indexing would panic when `front == T::len()` (iterator fully drained, or a
+		// zero-length array)
+		unsafe {
+			::core::slice::from_raw_parts_mut(T::raw_get_mut_ptr(&mut *self.data, front), len)
+		}
+	}
 }
 impl<T: Array> Iterator for Iter<T>
 {
 	type Item = T::Item;
 	fn next(&mut self) -> Option<T::Item>
 	{
-		if self.ofs == self.data.len() {
+		if self.front == self.back {
 			None
 		}
 		else {
-			// SAFE: Only ever read in sequence, and never dropped
+			// SAFE: Only ever read in sequence, and never double-read
 			let rv = Some(unsafe {
-				::core::ptr::read(self.data.get_ptr(self.ofs))
+				::core::ptr::read(self.data.get_ptr(self.front))
 				});
-			self.ofs += 1;
+			self.front += 1;
 			rv
 		}
 	}
+	fn size_hint(&self) -> (usize, Option<usize>)
+	{
+		let len = self.back - self.front;
+		(len, Some(len))
+	}
+}
+impl<T: Array> DoubleEndedIterator for Iter<T>
+{
+	fn next_back(&mut self) -> Option<T::Item>
+	{
+		if self.front == self.back {
+			None
+		}
+		else {
+			self.back -= 1;
+			// SAFE: Only ever read in sequence, and never double-read
+			Some(unsafe {
+				::core::ptr::read(self.data.get_ptr(self.back))
+				})
+		}
+	}
+}
+impl<T: Array> ExactSizeIterator for Iter<T>
+{
+	fn len(&self) -> usize {
+		self.back - self.front
+	}
+}
+impl<T: Array> ::core::iter::FusedIterator for Iter<T>
+{
 }
 impl<T: Array> Drop for Iter<T>
 {
 	fn drop(&mut self) {
-		for _ in self {
+		// SAFE: `front..back` is exactly the range of live, not-yet-read elements
+		for i in self.front .. self.back {
+			unsafe {
+				::core::ptr::drop_in_place(self.data.get_ptr(i) as *mut T::Item);
+			}
+		}
+	}
+}
+
+/// Drops the first `len` elements of a partially-initialised buffer on unwind/early-return
+struct InitGuard<T>
+{
+	ptr: *mut T,
+	len: usize,
+}
+impl<T> Drop for InitGuard<T>
+{
+	fn drop(&mut self) {
+		// SAFE: Caller maintains that `0 .. len` are initialised
+		unsafe {
+			for i in 0 .. self.len {
+				::core::ptr::drop_in_place(self.ptr.add(i));
+			}
 		}
 	}
 }
@@ -69,15 +179,66 @@ macro_rules! def {
 			type Item = T;
 			fn len(&self) -> usize { $s }
 			fn get_ptr(&self, i: usize) -> *const Self::Item { &self[i] }
+			fn get_mut_ptr(&mut self, i: usize) -> *mut Self::Item { &mut self[i] }
+			fn capacity() -> usize { $s }
+			unsafe fn raw_get_ptr(base: *const Self, ofs: usize) -> *const Self::Item {
+				(base as *const T).add(ofs)
+			}
+			unsafe fn raw_get_mut_ptr(base: *mut Self, ofs: usize) -> *mut Self::Item {
+				(base as *mut T).add(ofs)
+			}
+			fn from_fn<F>(mut cb: F) -> Self
+			where
+				F: FnMut(usize) -> Self::Item
+			{
+				let mut arr: [::core::mem::MaybeUninit<T>; $s] = unsafe { ::core::mem::MaybeUninit::uninit().assume_init() };
+				let mut guard = InitGuard { ptr: arr.as_mut_ptr() as *mut T, len: 0 };
+				// Bound via a local rather than the literal `$s` so a `$s == 0` instantiation
+				// doesn't expand to a statically-empty range
+				let n = $s;
+				for i in 0 .. n {
+					// SAFE: `i` is in-bounds, and the slot hasn't been written yet
+					unsafe { arr[i].as_mut_ptr().write(cb(i)); }
+					guard.len = i + 1;
+				}
+				::core::mem::forget(guard);
+				// SAFE: Every slot was just initialised above
+				unsafe { (&arr as *const _ as *const Self).read() }
+			}
+			fn try_from_fn<F, E>(mut cb: F) -> Result<Self, E>
+			where
+				F: FnMut(usize) -> Result<Self::Item, E>
+			{
+				let mut arr: [::core::mem::MaybeUninit<T>; $s] = unsafe { ::core::mem::MaybeUninit::uninit().assume_init() };
+				let mut guard = InitGuard { ptr: arr.as_mut_ptr() as *mut T, len: 0 };
+				// Bound via a local rather than the literal `$s` so a `$s == 0` instantiation
+				// doesn't expand to a statically-empty range
+				let n = $s;
+				for i in 0 .. n {
+					let v = match cb(i) {
+						Ok(v) => v,
+						// `guard` drops here, cleaning up the `0 .. i` slots already written
+						Err(e) => return Err(e),
+						};
+					// SAFE: `i` is in-bounds, and the slot hasn't been written yet
+					unsafe { arr[i].as_mut_ptr().write(v); }
+					guard.len = i + 1;
+				}
+				::core::mem::forget(guard);
+				// SAFE: Every slot was just initialised above
+				Ok(unsafe { (&arr as *const _ as *const Self).read() })
+			}
 		}
 		)+
 		};
 }
 impl<T: Array> ArrayExt for T {
 	fn into_iter(self) -> Iter<T> {
+		let back = self.len();
 		Iter {
 			data: ::core::mem::ManuallyDrop::new(self),
-			ofs: 0,
+			front: 0,
+			back,
 			}
 	}
 }
@@ -91,7 +252,7 @@ def! { 30 31 32 }
 #[cfg(test)]
 mod tests
 {
-	use ::ArrayExt;
+	use ::{Array, ArrayExt};
 
 	struct DropTrace<'a>(&'a ::core::cell::Cell<isize>);
 	impl<'a> Drop for DropTrace<'a>
@@ -128,4 +289,100 @@ mod tests
 		drop(it);
 		assert_eq!( v.get(), 2 );
 	}
+
+	#[test]
+	fn double_ended()
+	{
+		let mut it = [ 1, 2, 3, 4 ].into_iter();
+		assert_eq!( it.next(), Some(1) );
+		assert_eq!( it.next_back(), Some(4) );
+		assert_eq!( it.next_back(), Some(3) );
+		assert_eq!( it.next(), Some(2) );
+		assert_eq!( it.next(), None );
+		assert_eq!( it.next_back(), None );
+	}
+
+	#[test]
+	fn exact_size()
+	{
+		let mut it = [ 1, 2, 3 ].into_iter();
+		assert_eq!( it.len(), 3 );
+		it.next();
+		assert_eq!( it.len(), 2 );
+		it.next_back();
+		assert_eq!( it.len(), 1 );
+	}
+
+	#[test]
+	fn mixed_ends_drop()
+	{
+		let v = Default::default();
+		let mut it = [ DropTrace(&v), DropTrace(&v), DropTrace(&v) ].into_iter();
+		assert!( it.next().is_some() );
+		assert_eq!( v.get(), 1 );
+		assert!( it.next_back().is_some() );
+		assert_eq!( v.get(), 2 );
+		drop(it);
+		assert_eq!( v.get(), 3 );
+	}
+
+	#[test]
+	fn as_slice_shows_remaining()
+	{
+		let mut it = [ 1, 2, 3, 4 ].into_iter();
+		it.next();
+		assert_eq!( it.as_slice(), &[2, 3, 4] );
+		it.next_back();
+		assert_eq!( it.as_slice(), &[2, 3] );
+	}
+
+	#[test]
+	fn as_slice_empty_after_full_drain()
+	{
+		let mut it = [ 1, 2, 3 ].into_iter();
+		it.next();
+		it.next();
+		it.next();
+		assert_eq!( it.as_slice(), &[] as &[i32] );
+	}
+
+	#[test]
+	fn as_slice_on_zero_length_array()
+	{
+		assert_eq!( [(); 0].into_iter().as_slice(), &[] as &[()] );
+	}
+
+	#[test]
+	fn as_mut_slice_allows_editing()
+	{
+		let mut it = [ 1, 2, 3 ].into_iter();
+		it.next();
+		it.as_mut_slice()[0] = 42;
+		assert_eq!( it.next(), Some(42) );
+	}
+
+	#[test]
+	fn from_fn_fills_by_index()
+	{
+		let arr = <[u32; 5]>::from_fn(|i| i as u32 * 2);
+		assert_eq!( arr, [0, 2, 4, 6, 8] );
+	}
+
+	#[test]
+	fn try_from_fn_ok()
+	{
+		let arr = <[u32; 4]>::try_from_fn(|i| Ok::<_, ()>(i as u32));
+		assert_eq!( arr, Ok([0, 1, 2, 3]) );
+	}
+
+	#[test]
+	fn try_from_fn_cleans_up_on_error()
+	{
+		let v = Default::default();
+		let res = <[DropTrace; 4]>::try_from_fn(|i| {
+			if i == 2 { Err(()) } else { Ok(DropTrace(&v)) }
+			});
+		assert!( res.is_err() );
+		assert_eq!( v.get(), 2 );
+	}
 }